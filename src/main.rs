@@ -1,63 +1,643 @@
 use ratatui::{
     backend::{CrosstermBackend},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState},
     layout::{Layout, Constraint, Direction},
     style::{Style, Color},
     Terminal,
 };
-use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::event::{Event, EventStream, KeyCode, KeyModifiers};
 use crossterm::terminal::{enable_raw_mode, disable_raw_mode};
+use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
+use futures::{stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio_postgres::tls::MakeTlsConnect;
+use tokio_postgres::{AsyncMessage, Client, SimpleQueryMessage, Socket};
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{Certificate, Error as RustlsError, ServerName};
+use tokio_postgres::error::SqlState;
 use tokio_postgres::NoTls;
+use tokio_postgres_rustls::MakeRustlsConnect;
+use serde::Deserialize;
+use unicode_width::UnicodeWidthStr;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
 use std::{env, error::Error, io};
 use dotenv::dotenv;
 
+// How to protect the connection, mirroring libpq's `sslmode`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SslMode {
+    Disable,    // plain TCP, no encryption
+    Require,    // encrypt but don't validate the server certificate
+    VerifyFull, // encrypt and validate against the bundled Mozilla trust roots
+}
+
+// Connection parameters resolved from the config file or the environment, shared across every
+// per-database pool so the configured user/password are honored everywhere.
+#[derive(Clone)]
+struct ConnectionParams {
+    host: String,
+    port: u16,
+    user: String,
+    password: String,
+    sslmode: SslMode,
+}
+
+// A certificate verifier that accepts any chain, used for `sslmode=require`
+// where we want transport encryption without validating the server identity.
+struct AcceptAnyCert;
+
+impl ServerCertVerifier for AcceptAnyCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, RustlsError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+// Build a rustls-backed connector. With `verify` we validate against the
+// bundled Mozilla trust roots; otherwise we encrypt but trust any certificate.
+fn rustls_connector(verify: bool) -> MakeRustlsConnect {
+    let builder = rustls::ClientConfig::builder().with_safe_defaults();
+    let config = if verify {
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+            rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+                ta.subject,
+                ta.spki,
+                ta.name_constraints,
+            )
+        }));
+        builder.with_root_certificates(roots).with_no_client_auth()
+    } else {
+        builder
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCert))
+            .with_no_client_auth()
+    };
+    MakeRustlsConnect::new(config)
+}
+
+// Translate a low-level connection/auth failure into a short message for the
+// Results pane, walking the error's source chain for a Postgres SQLSTATE.
+fn friendly_error(err: &(dyn Error + 'static)) -> Option<String> {
+    let mut current: Option<&(dyn Error + 'static)> = Some(err);
+    while let Some(e) = current {
+        if let Some(db) = e.downcast_ref::<tokio_postgres::Error>() {
+            if db.code() == Some(&SqlState::INVALID_PASSWORD) {
+                return Some("Authentication failed: invalid password.".to_string());
+            }
+            if db.code() == Some(&SqlState::INVALID_AUTHORIZATION_SPECIFICATION) {
+                return Some(
+                    "Authentication failed: check the user, password and pg_hba.conf.".to_string(),
+                );
+            }
+            let message = db.to_string();
+            if message.contains("password") {
+                return Some(format!("Authentication failed: {}", message));
+            }
+        }
+        current = e.source();
+    }
+    None
+}
+
+// A single asynchronous notification received from the server.
+#[derive(Clone)]
+struct Notification {
+    channel: String,
+    payload: String,
+    received_at: chrono::DateTime<chrono::Local>,
+}
+
+// Open a dedicated connection for LISTEN/NOTIFY. Rather than discarding the
+// `Connection`, we drive it as a stream of `AsyncMessage`s and forward every
+// notification over `tx` so the UI can display them as they arrive. The
+// returned client is the one that must issue the `LISTEN` statements, since
+// notifications are only delivered on the connection that subscribed.
+async fn spawn_listener<T>(
+    config: &tokio_postgres::Config,
+    tls: T,
+    tx: mpsc::UnboundedSender<Notification>,
+) -> Result<Client, Box<dyn Error>>
+where
+    T: MakeTlsConnect<Socket>,
+    T::Stream: Send + 'static,
+    T::TlsConnect: Send,
+    <T::TlsConnect as tokio_postgres::tls::TlsConnect<Socket>>::Future: Send,
+{
+    let (client, mut connection) = config.connect(tls).await?;
+
+    // `poll_message` yields NoticeResponse/Notification messages that would
+    // otherwise be silently drained by the usual `connection.await` task.
+    let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+    tokio::spawn(async move {
+        while let Some(message) = messages.next().await {
+            match message {
+                Ok(AsyncMessage::Notification(note)) => {
+                    let notification = Notification {
+                        channel: note.channel().to_string(),
+                        payload: note.payload().to_string(),
+                        received_at: chrono::Local::now(),
+                    };
+                    // The UI end has gone away; nothing left to do but stop.
+                    if tx.send(notification).is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("Listener connection error: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    Ok(client)
+}
+
 // Function to fetch connection details from environment variables
-fn get_connection_string() -> Result<String, Box<dyn Error>> {
+fn get_connection_params() -> Result<ConnectionParams, Box<dyn Error>> {
     dotenv().ok(); // Load .env file
 
     // Read environment variables
     let user = env::var("POSTGRES_USER")?;
     let password = env::var("POSTGRES_PASSWORD")?;
     let host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".to_string());
-    let port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "5432".to_string());
+    let port = env::var("POSTGRES_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(5432);
+    let sslmode = match env::var("POSTGRES_SSLMODE").unwrap_or_default().as_str() {
+        "require" => SslMode::Require,
+        "verify-full" => SslMode::VerifyFull,
+        _ => SslMode::Disable,
+    };
+
+    Ok(ConnectionParams { host, port, user, password, sslmode })
+}
+
+// A named server entry, resolved either from the config file or the environment.
+struct NamedConnection {
+    name: String,
+    params: ConnectionParams,
+    database: Option<String>, // Database to connect to first, if specified
+}
+
+// Top-level shape of `~/.config/rustgresql/config.toml`.
+#[derive(Deserialize)]
+struct FileConfig {
+    #[serde(default)]
+    connections: Vec<ConnectionEntry>,
+}
+
+#[derive(Deserialize)]
+struct ConnectionEntry {
+    name: String,
+    host: String,
+    #[serde(default = "default_port")]
+    port: u16,
+    user: String,
+    #[serde(default)]
+    password: String,
+    database: Option<String>,
+    sslmode: Option<String>,
+}
+
+fn default_port() -> u16 {
+    5432
+}
+
+fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rustgresql").join("config.toml"))
+}
+
+// Load the saved connections from the TOML config, falling back to a single
+// environment-configured server when no config file is present.
+fn load_connections() -> Result<Vec<NamedConnection>, Box<dyn Error>> {
+    if let Some(path) = config_path() {
+        if path.exists() {
+            let text = std::fs::read_to_string(&path)?;
+            let config: FileConfig = toml::from_str(&text)?;
+            let connections: Vec<NamedConnection> = config
+                .connections
+                .into_iter()
+                .map(|entry| {
+                    let sslmode = match entry.sslmode.as_deref() {
+                        Some("require") => SslMode::Require,
+                        Some("verify-full") => SslMode::VerifyFull,
+                        _ => SslMode::Disable,
+                    };
+                    NamedConnection {
+                        name: entry.name,
+                        params: ConnectionParams {
+                            host: entry.host,
+                            port: entry.port,
+                            user: entry.user,
+                            password: entry.password,
+                            sslmode,
+                        },
+                        database: entry.database,
+                    }
+                })
+                .collect();
+            if !connections.is_empty() {
+                return Ok(connections);
+            }
+        }
+    }
+
+    // No usable config file: fall back to the environment-configured server.
+    let params = get_connection_params()?;
+    Ok(vec![NamedConnection { name: "default".to_string(), params, database: None }])
+}
+
+// Holds one connection pool per database. Pools are built lazily the first time
+// a database is opened and then reused, so navigating databases no longer spawns
+// a fresh TCP connection and background task on every keystroke.
+struct PoolManager {
+    params: ConnectionParams,
+    pools: HashMap<String, Pool>,
+}
+
+impl PoolManager {
+    fn new(params: ConnectionParams) -> Self {
+        Self { params, pools: HashMap::new() }
+    }
+
+    // Return the pool for `db_name`, creating and caching it on first use.
+    fn pool_for(&mut self, db_name: &str) -> Result<Pool, Box<dyn Error>> {
+        if let Some(pool) = self.pools.get(db_name) {
+            return Ok(pool.clone());
+        }
+
+        let mut pg_config = tokio_postgres::Config::new();
+        pg_config
+            .host(&self.params.host)
+            .port(self.params.port)
+            .user(&self.params.user)
+            .password(&self.params.password)
+            .dbname(db_name);
+
+        // deadpool erases the TLS connector type behind the Manager, so each
+        // `sslmode` arm yields the same `Pool` type and can share one cache.
+        let mgr_config = ManagerConfig { recycling_method: RecyclingMethod::Fast };
+        let manager = match self.params.sslmode {
+            SslMode::Disable => Manager::from_config(pg_config, NoTls, mgr_config),
+            SslMode::Require => Manager::from_config(pg_config, rustls_connector(false), mgr_config),
+            SslMode::VerifyFull => Manager::from_config(pg_config, rustls_connector(true), mgr_config),
+        };
+        let pool = Pool::builder(manager).build()?;
+
+        self.pools.insert(db_name.to_string(), pool);
+        Ok(self.pools[db_name].clone())
+    }
+}
+
+// A materialized result set: column headers plus the stringified cells returned
+// for one statement. The simple query protocol hands every value back as text,
+// so a SQL NULL surfaces as `None` and is rendered as an empty cell.
+struct QueryResult {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+// One block of output produced by a (possibly multi-statement) script run
+// through the simple query protocol: either a materialized result set or the
+// status line of a command that returned no rows (e.g. `INSERT`, `CREATE TABLE`).
+enum QueryBlock {
+    Table(QueryResult),
+    Status(String),
+}
+
+// One row of the schema→table tree shown in the Tables pane. Each variant
+// carries its indentation depth and a `visible` flag so collapsed schemas can
+// hide their tables without the rows being removed from the model.
+enum DatabaseTreeItem {
+    Database { name: String, indent: usize, visible: bool },
+    Schema { name: String, collapsed: bool, indent: usize, visible: bool },
+    Table {
+        #[allow(dead_code)]
+        schema: String,
+        name: String,
+        indent: usize,
+        visible: bool,
+    },
+}
+
+impl DatabaseTreeItem {
+    fn visible(&self) -> bool {
+        match self {
+            DatabaseTreeItem::Database { visible, .. }
+            | DatabaseTreeItem::Schema { visible, .. }
+            | DatabaseTreeItem::Table { visible, .. } => *visible,
+        }
+    }
+
+    fn indent(&self) -> usize {
+        match self {
+            DatabaseTreeItem::Database { indent, .. }
+            | DatabaseTreeItem::Schema { indent, .. }
+            | DatabaseTreeItem::Table { indent, .. } => *indent,
+        }
+    }
+
+    // The text drawn for this row, with a disclosure marker on schemas.
+    fn label(&self) -> String {
+        match self {
+            DatabaseTreeItem::Database { name, .. } => name.clone(),
+            DatabaseTreeItem::Schema { name, collapsed, .. } => {
+                let marker = if *collapsed { "▶" } else { "▼" };
+                format!("{} {}", marker, name)
+            }
+            DatabaseTreeItem::Table { name, .. } => name.clone(),
+        }
+    }
+}
 
-    Ok(format!(
-        "host={} port={} user={} password={}",
-        host, port, user, password
-    ))
+// An editable query buffer: a caret expressed as a byte offset into `buffer`,
+// multiline support, and a recalled history. Plain `Enter` executes the buffer
+// while `Shift`/`Alt+Enter` inserts a newline, so scripts can span many lines.
+struct QueryEditor {
+    buffer: String,
+    cursor: usize, // byte offset of the caret within `buffer`
+    history: Vec<String>, // previously executed queries, oldest first
+    history_index: Option<usize>, // position while cycling with Up/Down
+}
+
+impl QueryEditor {
+    fn new(history: Vec<String>) -> Self {
+        Self { buffer: String::new(), cursor: 0, history, history_index: None }
+    }
+
+    fn insert_char(&mut self, c: char) {
+        self.buffer.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    fn insert_newline(&mut self) {
+        self.insert_char('\n');
+    }
+
+    fn backspace(&mut self) {
+        if let Some(c) = self.buffer[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.buffer.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.buffer.len() {
+            self.buffer.remove(self.cursor);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if let Some(c) = self.buffer[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    fn move_right(&mut self) {
+        if let Some(c) = self.buffer[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    // Home/End move within the current line rather than the whole buffer.
+    fn move_home(&mut self) {
+        self.cursor = self.buffer[..self.cursor].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    }
+
+    fn move_end(&mut self) {
+        self.cursor = self.buffer[self.cursor..]
+            .find('\n')
+            .map(|offset| self.cursor + offset)
+            .unwrap_or(self.buffer.len());
+    }
+
+    // Recall the previous/next history entry. Stepping past the newest entry
+    // returns to an empty buffer so the user can type a fresh query.
+    fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match self.history_index {
+            Some(i) => i.saturating_sub(1),
+            None => self.history.len() - 1,
+        };
+        self.history_index = Some(index);
+        self.set_buffer(self.history[index].clone());
+    }
+
+    fn history_next(&mut self) {
+        match self.history_index {
+            Some(i) if i + 1 < self.history.len() => {
+                self.history_index = Some(i + 1);
+                self.set_buffer(self.history[i + 1].clone());
+            }
+            Some(_) => {
+                self.history_index = None;
+                self.set_buffer(String::new());
+            }
+            None => {}
+        }
+    }
+
+    fn set_buffer(&mut self, text: String) {
+        self.cursor = text.len();
+        self.buffer = text;
+    }
+
+    // Record the executed query in history (skipping blanks and immediate
+    // duplicates) and clear the buffer for the next one.
+    fn commit(&mut self) -> String {
+        let query = std::mem::take(&mut self.buffer);
+        if !query.trim().is_empty() && self.history.last() != Some(&query) {
+            self.history.push(query.clone());
+        }
+        self.cursor = 0;
+        self.history_index = None;
+        query
+    }
+
+    // Caret position as (column, row) in terminal cells, accounting for the
+    // display width of wide characters so the rendered cursor lines up.
+    fn cursor_position(&self) -> (u16, u16) {
+        let before = &self.buffer[..self.cursor];
+        let row = before.matches('\n').count() as u16;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let col = UnicodeWidthStr::width(&before[line_start..]) as u16;
+        (col, row)
+    }
+}
+
+// Where the executed-query history is persisted between sessions. Entries are
+// separated by NUL bytes so multiline queries round-trip intact.
+fn history_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rustgresql").join("history"))
+}
+
+fn load_history() -> Vec<String> {
+    history_path()
+        .filter(|path| path.exists())
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| text.split('\0').filter(|s| !s.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn save_history(history: &[String]) -> Result<(), Box<dyn Error>> {
+    if let Some(path) = history_path() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, history.join("\0"))?;
+    }
+    Ok(())
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum ActivePane {
+    Connections,
     Databases,
     Tables,
+    Notifications,
     Main,
     QueryInput,
 }
 
 struct AppState {
+    connections: Vec<NamedConnection>, // Servers from the config file, or the env fallback
+    selected_connection: Option<usize>,
     databases: Vec<String>,
     selected_database: Option<usize>,
-    tables: Vec<String>,
-    selected_table: Option<usize>, // Added for table navigation
-    query: String, // Store the current query input
-    query_result: String, // Store the result of the executed query
+    tables: Vec<DatabaseTreeItem>, // Flattened schema→table tree
+    selected_table: Option<usize>, // Cursor into the flattened tree
+    editor: QueryEditor, // Editable query buffer with cursor and history
+    query_result: String, // Status/error line shown when there is no table to draw
+    result_blocks: Vec<QueryBlock>, // One block per statement in the last script
+    result_block: usize, // Which block is currently shown in the Results pane
+    result_row: usize, // Vertical cursor into the current block's rows
+    result_col: usize, // Leftmost visible column for horizontal scrolling
+    pools: PoolManager, // One reused connection pool per database
+    listen_client: Option<Client>, // Dedicated connection that issues LISTENs
+    notifications: Vec<Notification>, // Running log of received notifications
+    channels: Vec<String>, // Channels we are currently subscribed to
+    selected_notification: Option<usize>, // Cursor into the notifications log
     active_pane: ActivePane,
 }
 
 impl AppState {
-    fn new(databases: Vec<String>) -> Self {
+    // Databases/tables start empty until `open_connection` populates them; `main`
+    // then chooses the starting pane (the picker when several servers exist).
+    fn new(connections: Vec<NamedConnection>) -> Self {
+        let pools = PoolManager::new(connections[0].params.clone());
         Self {
-            databases,
+            connections,
+            selected_connection: Some(0),
+            databases: vec![],
             selected_database: Some(0),
             tables: vec![],
             selected_table: Some(0), // Initialize table selection
-            query: String::new(),
+            editor: QueryEditor::new(load_history()),
             query_result: String::new(),
+            result_blocks: vec![],
+            result_block: 0,
+            result_row: 0,
+            result_col: 0,
+            pools,
+            listen_client: None,
+            notifications: vec![],
+            channels: vec![],
+            selected_notification: None,
             active_pane: ActivePane::Databases,
         }
     }
 
+    // The selectable rows of the Notifications pane, each paired with the
+    // channel it unlistens: every received notification, plus a placeholder for
+    // each subscribed channel that has not fired yet, so a subscription can be
+    // unlistened even before it delivers anything.
+    fn notification_rows(&self) -> Vec<(String, String)> {
+        let mut rows: Vec<(String, String)> = self
+            .notifications
+            .iter()
+            .map(|n| {
+                let label = format!(
+                    "{} [{}] {}",
+                    n.received_at.format("%H:%M:%S"),
+                    n.channel,
+                    n.payload
+                );
+                (label, n.channel.clone())
+            })
+            .collect();
+        for channel in &self.channels {
+            if !self.notifications.iter().any(|n| &n.channel == channel) {
+                rows.push((format!("[{}] (subscribed)", channel), channel.clone()));
+            }
+        }
+        rows
+    }
+
+    fn next_notification(&mut self) {
+        if let Some(selected) = self.selected_notification {
+            if selected + 1 < self.notification_rows().len() {
+                self.selected_notification = Some(selected + 1);
+            }
+        }
+    }
+
+    fn previous_notification(&mut self) {
+        if let Some(selected) = self.selected_notification {
+            if selected > 0 {
+                self.selected_notification = Some(selected - 1);
+            }
+        }
+    }
+
+    // Record a freshly received notification and keep the cursor sensible.
+    fn push_notification(&mut self, notification: Notification) {
+        self.notifications.push(notification);
+        if self.selected_notification.is_none() {
+            self.selected_notification = Some(0);
+        }
+    }
+
+    // Keep the Notifications cursor in range after the row set shrinks (e.g. a
+    // quiet subscription is unlistened away).
+    fn clamp_notification_selection(&mut self) {
+        let len = self.notification_rows().len();
+        self.selected_notification = match len {
+            0 => None,
+            _ => Some(self.selected_notification.unwrap_or(0).min(len - 1)),
+        };
+    }
+
+    fn next_connection(&mut self) {
+        if let Some(selected) = self.selected_connection {
+            if selected < self.connections.len() - 1 {
+                self.selected_connection = Some(selected + 1);
+            }
+        }
+    }
+
+    fn previous_connection(&mut self) {
+        if let Some(selected) = self.selected_connection {
+            if selected > 0 {
+                self.selected_connection = Some(selected - 1);
+            }
+        }
+    }
+
     fn next_database(&mut self) {
         if let Some(selected) = self.selected_database {
             if selected < self.databases.len() - 1 {
@@ -76,49 +656,139 @@ impl AppState {
 
     fn next_table(&mut self) {
         if let Some(selected) = self.selected_table {
-            if selected < self.tables.len() - 1 {
-                self.selected_table = Some(selected + 1);
+            let mut i = selected + 1;
+            while i < self.tables.len() {
+                if self.tables[i].visible() {
+                    self.selected_table = Some(i);
+                    return;
+                }
+                i += 1;
             }
         }
     }
 
     fn previous_table(&mut self) {
         if let Some(selected) = self.selected_table {
-            if selected > 0 {
-                self.selected_table = Some(selected - 1);
+            let mut i = selected;
+            while i > 0 {
+                i -= 1;
+                if self.tables[i].visible() {
+                    self.selected_table = Some(i);
+                    return;
+                }
             }
         }
     }
 
-    fn set_tables(&mut self, tables: Vec<String>) {
+    fn set_tables(&mut self, tables: Vec<DatabaseTreeItem>) {
         self.tables = tables;
         self.selected_table = Some(0); // Reset table selection when tables are updated
     }
+
+    // Collapse/expand the schema under the cursor, then recompute which rows
+    // are visible so `j`/`k` skip over the hidden tables.
+    fn toggle_selected_table(&mut self) {
+        if let Some(selected) = self.selected_table {
+            if let Some(DatabaseTreeItem::Schema { collapsed, .. }) = self.tables.get_mut(selected) {
+                *collapsed = !*collapsed;
+                self.recompute_table_visibility();
+            }
+        }
+    }
+
+    fn recompute_table_visibility(&mut self) {
+        let mut schema_collapsed = false;
+        for item in &mut self.tables {
+            match item {
+                DatabaseTreeItem::Database { visible, .. } => *visible = true,
+                DatabaseTreeItem::Schema { collapsed, visible, .. } => {
+                    *visible = true;
+                    schema_collapsed = *collapsed;
+                }
+                DatabaseTreeItem::Table { visible, .. } => *visible = !schema_collapsed,
+            }
+        }
+    }
+
+    fn set_result_blocks(&mut self, blocks: Vec<QueryBlock>) {
+        self.result_blocks = blocks;
+        self.result_block = 0; // Start at the first statement's output
+        self.result_row = 0; // Reset the cursor for the fresh result set
+        self.result_col = 0;
+    }
+
+    // The result set currently on screen, if the active block is a table rather
+    // than a command-status line.
+    fn current_table(&self) -> Option<&QueryResult> {
+        match self.result_blocks.get(self.result_block) {
+            Some(QueryBlock::Table(result)) => Some(result),
+            _ => None,
+        }
+    }
+
+    // Page forward/back through the blocks of a multi-statement script.
+    fn next_result_block(&mut self) {
+        if self.result_block + 1 < self.result_blocks.len() {
+            self.result_block += 1;
+            self.result_row = 0;
+            self.result_col = 0;
+        }
+    }
+
+    fn previous_result_block(&mut self) {
+        if self.result_block > 0 {
+            self.result_block -= 1;
+            self.result_row = 0;
+            self.result_col = 0;
+        }
+    }
+
+    fn next_result_row(&mut self) {
+        if let Some(results) = self.current_table() {
+            if self.result_row + 1 < results.rows.len() {
+                self.result_row += 1;
+            }
+        }
+    }
+
+    fn previous_result_row(&mut self) {
+        if self.result_row > 0 {
+            self.result_row -= 1;
+        }
+    }
+
+    fn scroll_result_right(&mut self) {
+        if let Some(results) = self.current_table() {
+            if self.result_col + 1 < results.columns.len() {
+                self.result_col += 1;
+            }
+        }
+    }
+
+    fn scroll_result_left(&mut self) {
+        if self.result_col > 0 {
+            self.result_col -= 1;
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    let connection_string = get_connection_string()?;
-    // Connect to PostgreSQL
-    let (client, connection) = tokio_postgres::connect(&connection_string, NoTls).await?;
-    tokio::spawn(async move {
-        if let Err(e) = connection.await {
-            eprintln!("Connection error: {}", e);
-        }
-    });
+    let connections = load_connections()?;
 
-    // Fetch the list of databases
-    let rows = client.query("SELECT datname FROM pg_database WHERE datistemplate = false", &[]).await?;
-    let databases: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+    // Channel over which listener tasks forward asynchronous notifications into
+    // the main loop so they can be appended to `AppState` between keypresses.
+    let (notif_tx, mut notif_rx) = mpsc::unbounded_channel::<Notification>();
 
-    // Initialize application state
-    let mut app_state = AppState::new(databases);
-
-    // Fetch initial tables for the first database
-    if let Some(selected) = app_state.selected_database {
-        let db_name = &app_state.databases[selected];
-        let tables = fetch_tables(db_name).await?;
-        app_state.set_tables(tables);
+    // With a single server we open it straight away; with several we start in
+    // the connection picker so the user chooses which server to browse.
+    let single_connection = connections.len() == 1;
+    let mut app_state = AppState::new(connections);
+    if single_connection {
+        open_connection(&mut app_state, 0, &notif_tx).await?;
+        app_state.active_pane = ActivePane::Databases;
+    } else {
+        app_state.active_pane = ActivePane::Connections;
     }
 
     // Initialize terminal
@@ -127,6 +797,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // Async source of terminal events so the main loop can `select!` between
+    // keypresses and incoming notifications.
+    let mut reader = EventStream::new();
+
     // Main loop
     loop {
         terminal.draw(|f| {
@@ -138,10 +812,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 .constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
                 .split(size);
 
-            // Left vertical layout for databases and tables
+            // Left vertical layout for databases, tables and notifications
             let vertical_chunks_left = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .constraints([
+                    Constraint::Percentage(40),
+                    Constraint::Percentage(40),
+                    Constraint::Percentage(20),
+                ])
                 .split(horizontal_chunks[0]);
 
             // Right vertical layout for main area and query input
@@ -175,15 +853,30 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black))
                 .highlight_symbol("> ");
 
-            // Sidebar for tables
-            let table_items: Vec<ListItem> = app_state
+            // Sidebar for tables: only visible tree rows are drawn, indented by
+            // depth, so the selection index must be mapped into the visible set.
+            let visible_rows: Vec<usize> = app_state
                 .tables
                 .iter()
-                .map(|table| ListItem::new(table.clone()))
+                .enumerate()
+                .filter(|(_, item)| item.visible())
+                .map(|(i, _)| i)
+                .collect();
+
+            let table_items: Vec<ListItem> = visible_rows
+                .iter()
+                .map(|&i| {
+                    let item = &app_state.tables[i];
+                    ListItem::new(format!("{}{}", "  ".repeat(item.indent()), item.label()))
+                })
                 .collect();
 
             let mut table_list_state = ListState::default();
-            table_list_state.select(app_state.selected_table);
+            table_list_state.select(
+                app_state
+                    .selected_table
+                    .and_then(|sel| visible_rows.iter().position(|&i| i == sel)),
+            );
 
             let table_sidebar = List::new(table_items)
                 .block(Block::default()
@@ -197,8 +890,31 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black))
                 .highlight_symbol("> ");
 
-            // Main content area
-            let main_area = Block::default()
+            // Sidebar for the live notifications log plus still-quiet
+            // subscriptions; selecting a row and pressing Enter unlistens it.
+            let notification_items: Vec<ListItem> = app_state
+                .notification_rows()
+                .into_iter()
+                .map(|(label, _)| ListItem::new(label))
+                .collect();
+
+            let mut notification_state = ListState::default();
+            notification_state.select(app_state.selected_notification);
+
+            let notification_sidebar = List::new(notification_items)
+                .block(Block::default()
+                    .title("Notifications")
+                    .borders(Borders::ALL)
+                    .style(if app_state.active_pane == ActivePane::Notifications {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    }))
+                .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black))
+                .highlight_symbol("> ");
+
+            // Main content block, shared by the table and the status fallback
+            let main_block = Block::default()
                 .title("Results")
                 .borders(Borders::ALL)
                 .style(if app_state.active_pane == ActivePane::Main {
@@ -208,7 +924,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 });
 
             // Query input area
-            let query_input = Paragraph::new(app_state.query.clone())
+            let query_input = Paragraph::new(app_state.editor.buffer.clone())
                 .block(Block::default()
                     .title("Enter Query")
                     .borders(Borders::ALL)
@@ -221,14 +937,120 @@ async fn main() -> Result<(), Box<dyn Error>> {
             // Render widgets
             f.render_stateful_widget(db_sidebar, vertical_chunks_left[0], &mut db_list_state);
             f.render_stateful_widget(table_sidebar, vertical_chunks_left[1], &mut table_list_state);
-            f.render_widget(main_area, vertical_chunks_right[0]);
+            f.render_stateful_widget(notification_sidebar, vertical_chunks_left[2], &mut notification_state);
+
+            // Before a server is chosen, the main area hosts the connection picker.
+            if app_state.active_pane == ActivePane::Connections {
+                let conn_items: Vec<ListItem> = app_state
+                    .connections
+                    .iter()
+                    .map(|c| ListItem::new(c.name.clone()))
+                    .collect();
+
+                let mut conn_state = ListState::default();
+                conn_state.select(app_state.selected_connection);
+
+                let conn_list = List::new(conn_items)
+                    .block(Block::default()
+                        .title("Connections")
+                        .borders(Borders::ALL)
+                        .style(Style::default().fg(Color::Yellow)))
+                    .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black))
+                    .highlight_symbol("> ");
+
+                f.render_stateful_widget(conn_list, vertical_chunks_right[0], &mut conn_state);
+                f.render_widget(query_input, vertical_chunks_right[1]);
+                return;
+            }
+
+            // Label the pane with the current block's position when a script
+            // produced several result sets, so paging has a visible anchor.
+            let main_block = if app_state.result_blocks.len() > 1 {
+                main_block.title(format!(
+                    "Results [{}/{}]",
+                    app_state.result_block + 1,
+                    app_state.result_blocks.len()
+                ))
+            } else {
+                main_block
+            };
+
+            // Render the current block: a scrollable table for result sets, a
+            // status line for commands, falling back to the last status/error
+            // message when no script has run yet.
+            match app_state.result_blocks.get(app_state.result_block) {
+                Some(QueryBlock::Table(results)) if !results.columns.is_empty() => {
+                    // Only draw the columns from `result_col` onwards so that h/l can
+                    // scroll horizontally across tables wider than the pane.
+                    let visible: Vec<usize> = (app_state.result_col..results.columns.len()).collect();
+                    let header = Row::new(
+                        visible.iter().map(|&c| Cell::from(results.columns[c].clone())),
+                    )
+                    .style(Style::default().fg(Color::Cyan));
+
+                    let body = results.rows.iter().map(|row| {
+                        Row::new(visible.iter().map(|&c| {
+                            Cell::from(row.get(c).cloned().unwrap_or_default())
+                        }))
+                    });
+
+                    let widths: Vec<Constraint> = visible
+                        .iter()
+                        .map(|_| Constraint::Percentage((100 / visible.len().max(1)) as u16))
+                        .collect();
+
+                    let table = Table::new(body)
+                        .header(header)
+                        .block(main_block)
+                        .widths(&widths)
+                        .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black))
+                        .highlight_symbol("> ");
+
+                    let mut results_state = TableState::default();
+                    results_state.select(Some(app_state.result_row));
+                    f.render_stateful_widget(table, vertical_chunks_right[0], &mut results_state);
+                }
+                Some(QueryBlock::Status(line)) => {
+                    let status = Paragraph::new(line.clone()).block(main_block);
+                    f.render_widget(status, vertical_chunks_right[0]);
+                }
+                _ => {
+                    let status = Paragraph::new(app_state.query_result.clone()).block(main_block);
+                    f.render_widget(status, vertical_chunks_right[0]);
+                }
+            }
+
             f.render_widget(query_input, vertical_chunks_right[1]);
+
+            // Place the terminal cursor inside the query box when it is focused,
+            // offset by one for the surrounding border.
+            if app_state.active_pane == ActivePane::QueryInput {
+                let area = vertical_chunks_right[1];
+                let (col, row) = app_state.editor.cursor_position();
+                f.set_cursor(area.x + 1 + col, area.y + 1 + row);
+            }
         })?;
 
-        // Handle input
-        if let Event::Key(key) = event::read()? {
+        // Wait for either a terminal event or an incoming notification, so the
+        // UI redraws when a NOTIFY arrives even without a keypress.
+        let key = tokio::select! {
+            maybe_event = reader.next() => {
+                match maybe_event {
+                    Some(Ok(Event::Key(key))) => key,
+                    Some(Ok(_)) => continue, // resize/mouse: just redraw
+                    Some(Err(err)) => return Err(err.into()),
+                    None => break,
+                }
+            }
+            Some(notification) = notif_rx.recv() => {
+                app_state.push_notification(notification);
+                continue;
+            }
+        };
+
+        {
             match (key.code, key.modifiers) {
-                // Switch panes with Ctrl + hjkl
+                // Switch panes with Ctrl + hjkl (plus Ctrl+n for notifications)
                 (KeyCode::Char('h'), KeyModifiers::CONTROL) => {
                     app_state.active_pane = ActivePane::Databases;
                 }
@@ -241,47 +1063,205 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 (KeyCode::Char('l'), KeyModifiers::CONTROL) => {
                     app_state.active_pane = ActivePane::QueryInput;
                 }
+                (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                    app_state.active_pane = ActivePane::Notifications;
+                }
 
                 // Navigation within panes using hjkl
                 (KeyCode::Char('j'), KeyModifiers::NONE) => {
-                    if app_state.active_pane == ActivePane::Databases {
+                    if app_state.active_pane == ActivePane::Connections {
+                        app_state.next_connection();
+                    } else if app_state.active_pane == ActivePane::Databases {
                         app_state.next_database();
                         if let Some(selected) = app_state.selected_database {
-                            let db_name = &app_state.databases[selected];
-                            let tables = fetch_tables(db_name).await?;
+                            let db_name = app_state.databases[selected].clone();
+                            let pool = app_state.pools.pool_for(&db_name)?;
+                            let tables = fetch_tables(&pool, &db_name).await?;
                             app_state.set_tables(tables);
                         }
                     } else if app_state.active_pane == ActivePane::Tables {
                         app_state.next_table();
+                    } else if app_state.active_pane == ActivePane::Notifications {
+                        app_state.next_notification();
+                    } else if app_state.active_pane == ActivePane::Main {
+                        app_state.next_result_row();
+                    } else if app_state.active_pane == ActivePane::QueryInput {
+                        // In the editor `j` is just a character, not navigation.
+                        app_state.editor.insert_char('j');
                     }
                 }
                 (KeyCode::Char('k'), KeyModifiers::NONE) => {
-                    if app_state.active_pane == ActivePane::Databases {
+                    if app_state.active_pane == ActivePane::Connections {
+                        app_state.previous_connection();
+                    } else if app_state.active_pane == ActivePane::Databases {
                         app_state.previous_database();
                         if let Some(selected) = app_state.selected_database {
-                            let db_name = &app_state.databases[selected];
-                            let tables = fetch_tables(db_name).await?;
+                            let db_name = app_state.databases[selected].clone();
+                            let pool = app_state.pools.pool_for(&db_name)?;
+                            let tables = fetch_tables(&pool, &db_name).await?;
                             app_state.set_tables(tables);
                         }
                     } else if app_state.active_pane == ActivePane::Tables {
                         app_state.previous_table();
+                    } else if app_state.active_pane == ActivePane::Notifications {
+                        app_state.previous_notification();
+                    } else if app_state.active_pane == ActivePane::Main {
+                        app_state.previous_result_row();
+                    } else if app_state.active_pane == ActivePane::QueryInput {
+                        // In the editor `k` is just a character, not navigation.
+                        app_state.editor.insert_char('k');
+                    }
+                }
+                // Horizontal scrolling across wide result tables
+                (KeyCode::Char('h'), KeyModifiers::NONE) if app_state.active_pane == ActivePane::Main => {
+                    app_state.scroll_result_left();
+                }
+                (KeyCode::Char('l'), KeyModifiers::NONE) if app_state.active_pane == ActivePane::Main => {
+                    app_state.scroll_result_right();
+                }
+                // Page between the result blocks of a multi-statement script
+                (KeyCode::PageDown, _) if app_state.active_pane == ActivePane::Main => {
+                    app_state.next_result_block();
+                }
+                (KeyCode::PageUp, _) if app_state.active_pane == ActivePane::Main => {
+                    app_state.previous_result_block();
+                }
+                // Open the highlighted server from the connection picker
+                (KeyCode::Enter, _) if app_state.active_pane == ActivePane::Connections => {
+                    if let Some(index) = app_state.selected_connection {
+                        match open_connection(&mut app_state, index, &notif_tx).await {
+                            Ok(()) => app_state.active_pane = ActivePane::Databases,
+                            Err(err) => {
+                                app_state.query_result = friendly_error(err.as_ref())
+                                    .unwrap_or_else(|| format!("Error: {}", err));
+                            }
+                        }
                     }
                 }
-                // Handle other input (e.g., query input)
+                // Collapse/expand the selected schema in the tree
+                (KeyCode::Enter, _) if app_state.active_pane == ActivePane::Tables => {
+                    app_state.toggle_selected_table();
+                }
+                (KeyCode::Char(' '), KeyModifiers::NONE)
+                    if app_state.active_pane == ActivePane::Tables =>
+                {
+                    app_state.toggle_selected_table();
+                }
+                // Unlisten the channel of the selected row in the pane
+                (KeyCode::Enter, _) if app_state.active_pane == ActivePane::Notifications => {
+                    let channel = app_state
+                        .selected_notification
+                        .and_then(|index| app_state.notification_rows().into_iter().nth(index))
+                        .map(|(_, channel)| channel);
+                    if let Some(channel) = channel {
+                        if let Some(client) = &app_state.listen_client {
+                            let statement = format!("UNLISTEN \"{}\"", channel);
+                            match client.batch_execute(&statement).await {
+                                Ok(()) => {
+                                    app_state.channels.retain(|c| c != &channel);
+                                    app_state.query_result = format!("Unlistened {}", channel);
+                                }
+                                Err(err) => {
+                                    app_state.query_result = format!("Error: {}", err);
+                                }
+                            }
+                        }
+                        app_state.clamp_notification_selection();
+                    }
+                }
+                // Insert a newline instead of executing: Shift/Alt+Enter lets a
+                // script span several lines.
+                (KeyCode::Enter, KeyModifiers::SHIFT)
+                | (KeyCode::Enter, KeyModifiers::ALT)
+                    if app_state.active_pane == ActivePane::QueryInput =>
+                {
+                    app_state.editor.insert_newline();
+                }
+                // Edit the query buffer: text input and intra-buffer navigation.
                 (KeyCode::Char(c), _) if app_state.active_pane == ActivePane::QueryInput => {
-                    app_state.query.push(c);
+                    app_state.editor.insert_char(c);
                 }
                 (KeyCode::Backspace, _) if app_state.active_pane == ActivePane::QueryInput => {
-                    app_state.query.pop();
+                    app_state.editor.backspace();
+                }
+                (KeyCode::Delete, _) if app_state.active_pane == ActivePane::QueryInput => {
+                    app_state.editor.delete();
+                }
+                (KeyCode::Left, _) if app_state.active_pane == ActivePane::QueryInput => {
+                    app_state.editor.move_left();
+                }
+                (KeyCode::Right, _) if app_state.active_pane == ActivePane::QueryInput => {
+                    app_state.editor.move_right();
+                }
+                (KeyCode::Home, _) if app_state.active_pane == ActivePane::QueryInput => {
+                    app_state.editor.move_home();
+                }
+                (KeyCode::End, _) if app_state.active_pane == ActivePane::QueryInput => {
+                    app_state.editor.move_end();
+                }
+                // Cycle previously executed queries through the buffer.
+                (KeyCode::Up, _) if app_state.active_pane == ActivePane::QueryInput => {
+                    app_state.editor.history_prev();
+                }
+                (KeyCode::Down, _) if app_state.active_pane == ActivePane::QueryInput => {
+                    app_state.editor.history_next();
                 }
                 (KeyCode::Enter, _) if app_state.active_pane == ActivePane::QueryInput => {
-                    if let Some(selected) = app_state.selected_database {
-                        let db_name = &app_state.databases[selected];
-                        let result = execute_query(db_name, &app_state.query).await;
-                        app_state.query_result = match result {
-                            Ok(res) => res,
-                            Err(err) => format!("Error: {}", err),
-                        };
+                    let query = app_state.editor.commit();
+                    let trimmed = query.trim().to_string();
+                    let lowered = trimmed.to_ascii_lowercase();
+                    if lowered.starts_with("listen ") || lowered.starts_with("unlisten ") {
+                        // LISTEN/UNLISTEN must run on the dedicated connection that
+                        // owns the subscription, not on a pooled client.
+                        if let Some(client) = &app_state.listen_client {
+                            match client.batch_execute(&trimmed).await {
+                                Ok(()) => {
+                                    // Track the channel so the pane reflects the
+                                    // current subscriptions.
+                                    let channel = trimmed
+                                        .split_whitespace()
+                                        .nth(1)
+                                        .unwrap_or("")
+                                        .trim_matches('"')
+                                        .to_string();
+                                    if lowered.starts_with("listen ") {
+                                        if !app_state.channels.contains(&channel) {
+                                            app_state.channels.push(channel.clone());
+                                        }
+                                        // Surface the new subscription as a
+                                        // selectable row straight away.
+                                        if app_state.selected_notification.is_none() {
+                                            app_state.selected_notification = Some(0);
+                                        }
+                                        app_state.query_result = format!("Listening on {}", channel);
+                                    } else {
+                                        app_state.channels.retain(|c| c != &channel);
+                                        app_state.clamp_notification_selection();
+                                        app_state.query_result = format!("Unlistened {}", channel);
+                                    }
+                                }
+                                Err(err) => {
+                                    app_state.query_result = format!("Error: {}", err);
+                                }
+                            }
+                        } else {
+                            app_state.query_result =
+                                "No active connection for LISTEN/NOTIFY.".to_string();
+                        }
+                    } else if let Some(selected) = app_state.selected_database {
+                        let db_name = app_state.databases[selected].clone();
+                        let pool = app_state.pools.pool_for(&db_name)?;
+                        match execute_query(&pool, &query).await {
+                            Ok(blocks) => {
+                                app_state.query_result.clear();
+                                app_state.set_result_blocks(blocks);
+                            }
+                            Err(err) => {
+                                app_state.set_result_blocks(vec![]);
+                                app_state.query_result = friendly_error(err.as_ref())
+                                    .unwrap_or_else(|| format!("Error: {}", err));
+                            }
+                        }
                     }
                 }
                 // Quit
@@ -292,32 +1272,171 @@ async fn main() -> Result<(), Box<dyn Error>> {
     }
 
     disable_raw_mode()?;
+    save_history(&app_state.editor.history)?;
     Ok(())
 }
 
-async fn fetch_tables(db_name: &str) -> Result<Vec<String>, Box<dyn Error>> {
-    let connection_string = format!("host=localhost user=postgres password=postgres dbname={}", db_name);
-    let (client, connection) = tokio_postgres::connect(&connection_string, NoTls).await?;
-    tokio::spawn(async move {
-        if let Err(e) = connection.await {
-            eprintln!("Connection error: {}", e);
+// Open a saved connection: build its pools, list databases, and load the tables
+// of the first database. Leaves `app_state` ready to browse that server.
+async fn open_connection(
+    app_state: &mut AppState,
+    index: usize,
+    notif_tx: &mpsc::UnboundedSender<Notification>,
+) -> Result<(), Box<dyn Error>> {
+    let params = app_state.connections[index].params.clone();
+    // libpq defaults `dbname` to the user name, so use the configured database
+    // when present and otherwise fall back to the user name.
+    let bootstrap_db = app_state.connections[index]
+        .database
+        .clone()
+        .unwrap_or_else(|| params.user.clone());
+
+    let mut pools = PoolManager::new(params.clone());
+    let bootstrap = pools.pool_for(&bootstrap_db)?;
+    let client = bootstrap.get().await?;
+    let rows = client
+        .query("SELECT datname FROM pg_database WHERE datistemplate = false", &[])
+        .await?;
+    let databases: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+    drop(client);
+
+    // Open a dedicated listener connection for this server so the user can
+    // LISTEN on channels and watch notifications stream into the pane.
+    let mut pg_config = tokio_postgres::Config::new();
+    pg_config
+        .host(&params.host)
+        .port(params.port)
+        .user(&params.user)
+        .password(&params.password)
+        .dbname(&bootstrap_db);
+    let listen_client = match params.sslmode {
+        SslMode::Disable => spawn_listener(&pg_config, NoTls, notif_tx.clone()).await?,
+        SslMode::Require => {
+            spawn_listener(&pg_config, rustls_connector(false), notif_tx.clone()).await?
         }
-    });
+        SslMode::VerifyFull => {
+            spawn_listener(&pg_config, rustls_connector(true), notif_tx.clone()).await?
+        }
+    };
+
+    app_state.pools = pools;
+    app_state.databases = databases;
+    app_state.selected_database = Some(0);
+    app_state.selected_connection = Some(index);
+    app_state.listen_client = Some(listen_client);
+    app_state.notifications.clear();
+    app_state.channels.clear();
+    app_state.selected_notification = None;
+
+    // Fetch initial tables for the first database
+    if let Some(selected) = app_state.selected_database {
+        let db_name = app_state.databases[selected].clone();
+        let pool = app_state.pools.pool_for(&db_name)?;
+        let tables = fetch_tables(&pool, &db_name).await?;
+        app_state.set_tables(tables);
+    }
 
-    let query = "SELECT tablename FROM pg_tables WHERE schemaname = 'public'";
+    Ok(())
+}
+
+async fn fetch_tables(pool: &Pool, db_name: &str) -> Result<Vec<DatabaseTreeItem>, Box<dyn Error>> {
+    let client = pool.get().await?;
+    // `information_schema.tables` exposes every schema, not just `public`.
+    let query = "SELECT table_schema, table_name \
+                 FROM information_schema.tables \
+                 WHERE table_type = 'BASE TABLE' \
+                 ORDER BY table_schema, table_name";
     let rows = client.query(query, &[]).await?;
-    Ok(rows.iter().map(|row| row.get(0)).collect())
+
+    // Build the flattened tree: a database root, then each schema with its
+    // tables nested one level deeper. Everything starts expanded and visible.
+    let mut items = vec![DatabaseTreeItem::Database {
+        name: db_name.to_string(),
+        indent: 0,
+        visible: true,
+    }];
+    let mut current_schema: Option<String> = None;
+    for row in &rows {
+        let schema: String = row.get(0);
+        let table: String = row.get(1);
+        if current_schema.as_deref() != Some(schema.as_str()) {
+            items.push(DatabaseTreeItem::Schema {
+                name: schema.clone(),
+                collapsed: false,
+                indent: 1,
+                visible: true,
+            });
+            current_schema = Some(schema.clone());
+        }
+        items.push(DatabaseTreeItem::Table {
+            schema: schema.clone(),
+            name: table,
+            indent: 2,
+            visible: true,
+        });
+    }
+
+    Ok(items)
 }
 
-async fn execute_query(db_name: &str, query: &str) -> Result<String, Box<dyn Error>> {
-    let connection_string = format!("host=localhost user=postgres password=postgres dbname={}", db_name);
-    let (client, connection) = tokio_postgres::connect(&connection_string, NoTls).await?;
-    tokio::spawn(async move {
-        if let Err(e) = connection.await {
-            eprintln!("Connection error: {}", e);
+// Run one or more `;`-separated statements through the simple query protocol.
+// Unlike `client.query`, `simple_query` accepts multi-statement scripts and
+// non-SELECT DDL, returning a `RowDescription` and a run of `Row` messages per
+// query plus a `CommandComplete` per statement. We fold those into a block per
+// statement: a result table for queries, a status line for everything else.
+async fn execute_query(pool: &Pool, query: &str) -> Result<Vec<QueryBlock>, Box<dyn Error>> {
+    let client = pool.get().await?;
+    let messages = client.simple_query(query).await?;
+
+    let mut blocks = Vec::new();
+    // A query's columns arrive in a `RowDescription` ahead of its rows, so a
+    // SELECT returning zero rows still carries a header. `pending` holds the
+    // in-progress result set until its `CommandComplete` closes it off.
+    let mut pending: Option<(Vec<String>, Vec<Vec<String>>)> = None;
+    for message in messages {
+        match message {
+            SimpleQueryMessage::RowDescription(columns) => {
+                let names = columns.iter().map(|c| c.name().to_string()).collect();
+                pending = Some((names, Vec::new()));
+            }
+            SimpleQueryMessage::Row(row) => {
+                // The simple query protocol hands back every value already
+                // rendered in the server's canonical text form, with `None` for
+                // SQL NULL, so no per-type decoding is needed and a NULL simply
+                // becomes an empty cell here.
+                let cells = (0..row.columns().len())
+                    .map(|i| row.get(i).unwrap_or("").to_string())
+                    .collect();
+                match &mut pending {
+                    Some((_, rows)) => rows.push(cells),
+                    None => {
+                        // Defensive: older servers may omit the row description.
+                        let columns =
+                            row.columns().iter().map(|c| c.name().to_string()).collect();
+                        pending = Some((columns, vec![cells]));
+                    }
+                }
+            }
+            SimpleQueryMessage::CommandComplete(rows_affected) => match pending.take() {
+                // A statement that produced a row description is a query: emit its
+                // table even when the body is empty, so the header still shows.
+                Some((columns, rows)) => {
+                    blocks.push(QueryBlock::Table(QueryResult { columns, rows }));
+                }
+                // Otherwise it was a command (DDL/DML). tokio-postgres surfaces
+                // only the affected-row count on `CommandComplete`, not the
+                // command tag (`INSERT`/`CREATE TABLE`/…), so we report the count
+                // alone rather than fabricate a tag we can't observe.
+                None => {
+                    blocks.push(QueryBlock::Status(format!(
+                        "Command complete ({} rows affected)",
+                        rows_affected
+                    )));
+                }
+            },
+            _ => {}
         }
-    });
+    }
 
-    let rows = client.query(query, &[]).await?;
-    Ok(format!("Executed query successfully. Rows returned: {}", rows.len()))
+    Ok(blocks)
 }